@@ -1,14 +1,118 @@
 use macroquad::prelude::*;
 use snake3::{
-    named,
-    snake::{Apple, SnakeDirection},
+    snake::{Apple, Entity, SnakeDirection},
     SnakeGame,
 };
+use std::collections::{HashSet, VecDeque};
 
 // Game constants
 const GRID_WIDTH: f32 = 20.0;
 const BASE_UPDATE_INTERVAL: f32 = 0.5;
 const MIN_MOVE_INTERVAL: f32 = 0.1;
+/// Default amount the movement interval shrinks per point scored
+const SPEED_STEP: f32 = 0.02;
+/// Number of movement ticks a bonus fruit stays on the board before despawning
+const BONUS_FRUIT_LIFETIME: u32 = 30;
+/// Score awarded for eating a bonus fruit, versus +1 for a normal apple
+const BONUS_FRUIT_SCORE: u32 = 5;
+/// Chance, per movement step with no bonus fruit on the board, that one is rolled
+const BONUS_FRUIT_CHANCE: f32 = 0.05;
+/// Number of static obstacles placed on the board at game start
+const OBSTACLE_COUNT: usize = 3;
+/// File the high score is persisted to on native targets
+const HIGH_SCORE_FILE: &str = "snake4_high_score.txt";
+
+/// Reads the persisted high score, or 0 if none has been saved yet
+#[cfg(not(target_arch = "wasm32"))]
+fn load_high_score() -> u32 {
+    std::fs::read_to_string(HIGH_SCORE_FILE)
+        .ok()
+        .and_then(|contents| contents.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// The web/wasm target has no writable filesystem, so it never has a saved score
+#[cfg(target_arch = "wasm32")]
+fn load_high_score() -> u32 {
+    0
+}
+
+/// Writes `high_score` to disk so it survives across runs
+#[cfg(not(target_arch = "wasm32"))]
+fn store_high_score(high_score: u32) {
+    _ = std::fs::write(HIGH_SCORE_FILE, high_score.to_string());
+}
+
+/// No-op on the web/wasm target
+#[cfg(target_arch = "wasm32")]
+fn store_high_score(_high_score: u32) {}
+
+/// Returns true if `a` and `b` are directly opposite headings.
+fn is_opposite(a: SnakeDirection, b: SnakeDirection) -> bool {
+    matches!(
+        (a, b),
+        (SnakeDirection::Up, SnakeDirection::Down)
+            | (SnakeDirection::Down, SnakeDirection::Up)
+            | (SnakeDirection::Left, SnakeDirection::Right)
+            | (SnakeDirection::Right, SnakeDirection::Left)
+    )
+}
+
+/// A short-lived fruit worth more than a normal apple; despawns if not eaten in time
+struct BonusFruit {
+    x: i16,
+    y: i16,
+    ticks_left: u32,
+}
+
+impl BonusFruit {
+    fn new(x: i16, y: i16) -> Self {
+        Self {
+            x,
+            y,
+            ticks_left: BONUS_FRUIT_LIFETIME,
+        }
+    }
+}
+
+impl Entity for BonusFruit {
+    fn x(&self) -> i16 {
+        self.x
+    }
+
+    fn y(&self) -> i16 {
+        self.y
+    }
+}
+
+/// A static hazard cell; colliding with it ends the game
+struct Obstacle {
+    x: i16,
+    y: i16,
+}
+
+impl Obstacle {
+    fn new(x: i16, y: i16) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Entity for Obstacle {
+    fn x(&self) -> i16 {
+        self.x
+    }
+
+    fn y(&self) -> i16 {
+        self.y
+    }
+}
+
+/// Whether leaving the board edge is lethal or wraps to the opposite side
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BoardMode {
+    Walls,
+    WrapAround,
+}
 
 /// Main game state manager
 struct Game {
@@ -22,39 +126,136 @@ struct Game {
     score: u32,
     /// High score record
     high_score: u32,
+    /// Pending direction changes, applied one per movement step
+    turn_queue: VecDeque<SnakeDirection>,
+    /// The heading the snake is currently committed to
+    heading: SnakeDirection,
+    /// Movement interval used at score 0, before any speed-up is applied
+    start_interval: f32,
+    /// How much the movement interval shrinks per point scored
+    speed_step: f32,
+    /// Board width in cells
+    columns: i16,
+    /// Board height in cells
+    rows: i16,
+    /// Set once the board has no free cell left to spawn food on
+    won: bool,
+    /// Whether the board edges are lethal walls or wrap around
+    board_mode: BoardMode,
 }
 
 impl Game {
-    /// Create a new game with specified grid dimensions
-    pub fn new(columns: i16, rows: i16) -> Self {
-        Self {
+    /// Create a new game with specified grid dimensions and board mode
+    pub fn new(columns: i16, rows: i16, board_mode: BoardMode) -> Self {
+        let mut game = Self {
             snake_game: SnakeGame::new(columns, rows, None, None),
             update_timer: 0.0,
             last_move_time: 0.0,
             score: 0,
             high_score: 0,
+            turn_queue: VecDeque::new(),
+            heading: SnakeDirection::Right,
+            start_interval: BASE_UPDATE_INTERVAL,
+            speed_step: SPEED_STEP,
+            columns,
+            rows,
+            won: false,
+            board_mode,
+        };
+
+        for _ in 0..OBSTACLE_COUNT {
+            game.spawn_entity(Obstacle::new);
+        }
+
+        game
+    }
+
+    /// Current automatic movement interval, shrinking as the score rises
+    fn move_interval(&self) -> f32 {
+        (self.start_interval - self.speed_step * self.score as f32).max(MIN_MOVE_INTERVAL)
+    }
+
+    /// In `WrapAround` mode, brings a head that has left the board back onto
+    /// the opposite edge so `check_collisions` is left to detect only
+    /// self-collision (obstacles are still checked separately)
+    fn wrap_head(&mut self) {
+        if self.board_mode != BoardMode::WrapAround {
+            return;
+        }
+
+        if let Some(head) = self.snake_game.snake.body.front_mut() {
+            head.x = head.x.rem_euclid(self.columns);
+            head.y = head.y.rem_euclid(self.rows);
         }
     }
 
-    /// Handle player input and return whether movement occurred
+    /// Cells occupied by the snake's body plus any entity matching `include`
+    fn occupied_cells(&self, include: impl Fn(&dyn Entity) -> bool) -> HashSet<(i16, i16)> {
+        self.snake_game
+            .snake
+            .body
+            .iter()
+            .map(|segment| (segment.x, segment.y))
+            .chain(
+                self.snake_game
+                    .entities
+                    .iter()
+                    .filter(|entity| include(entity.as_ref()))
+                    .map(|entity| (entity.x(), entity.y())),
+            )
+            .collect()
+    }
+
+    /// Places a new entity built by `make` on a uniformly random free cell.
+    /// Returns `false` if the board has no free cell left.
+    fn spawn_entity<T, F>(&mut self, make: F) -> bool
+    where
+        T: Entity + 'static,
+        F: FnOnce(i16, i16) -> T,
+    {
+        let occupied = self.occupied_cells(|_| true);
+        let free: Vec<(i16, i16)> = (0..self.columns)
+            .flat_map(|x| (0..self.rows).map(move |y| (x, y)))
+            .filter(|cell| !occupied.contains(cell))
+            .collect();
+
+        if free.is_empty() {
+            return false;
+        }
+
+        let (x, y) = free[rand::gen_range(0, free.len())];
+        self.snake_game.entities.push(Box::new(make(x, y)));
+        true
+    }
+
+    /// True once the snake's body and the permanent obstacles leave no free
+    /// cell, i.e. even once any transient bonus fruit despawns there is
+    /// nowhere left to spawn food. Used to tell a genuinely full board apart
+    /// from a bonus fruit merely sitting on the last free cell for now.
+    fn board_full(&self) -> bool {
+        let occupied = self.occupied_cells(|entity| entity.downcast_ref::<Obstacle>().is_some());
+        occupied.len() as i16 >= self.columns * self.rows
+    }
+
+    /// Queue up any direction key presses and return whether one was queued
     pub fn handle_input(&mut self) -> bool {
-        let mut moved = false;
+        let mut queued = false;
 
         if is_key_pressed(KeyCode::Right) {
-            self.snake_game.snake.set_direction(SnakeDirection::Right);
-            moved = true;
+            self.turn_queue.push_back(SnakeDirection::Right);
+            queued = true;
         } else if is_key_pressed(KeyCode::Left) {
-            self.snake_game.snake.set_direction(SnakeDirection::Left);
-            moved = true;
+            self.turn_queue.push_back(SnakeDirection::Left);
+            queued = true;
         } else if is_key_pressed(KeyCode::Down) {
-            self.snake_game.snake.set_direction(SnakeDirection::Up);
-            moved = true;
+            self.turn_queue.push_back(SnakeDirection::Down);
+            queued = true;
         } else if is_key_pressed(KeyCode::Up) {
-            self.snake_game.snake.set_direction(SnakeDirection::Down);
-            moved = true;
+            self.turn_queue.push_back(SnakeDirection::Up);
+            queued = true;
         }
 
-        moved
+        queued
     }
 
     /// Advance the game state by one tick
@@ -64,28 +265,70 @@ impl Game {
         let input_moved = self.handle_input();
 
         // Determine if we should move based on either input or timer
+        let move_interval = self.move_interval();
         let should_move = (input_moved && (current_time - self.last_move_time) >= MIN_MOVE_INTERVAL)
-            || (!input_moved && self.update_timer >= BASE_UPDATE_INTERVAL);
+            || (!input_moved && self.update_timer >= move_interval);
 
         if should_move {
+            if let Some(next_direction) = self.turn_queue.pop_front() {
+                if !is_opposite(next_direction, self.heading) {
+                    self.snake_game.snake.set_direction(next_direction);
+                    self.heading = next_direction;
+                }
+            }
+
             self.snake_game.snake.advance();
+            self.wrap_head();
 
             // Handle collisions
             if self.snake_game.check_collisions() {
                 return false; // Game over
             }
 
-            // Handle apple eating
+            // Handle eating/hazard collisions
             if let Some(hit) = self.snake_game.check_entity_collision() {
                 if hit.downcast_ref::<Apple>().is_some() {
                     self.snake_game.snake.grow();
                     self.score += 1;
+                } else if hit.downcast_ref::<BonusFruit>().is_some() {
+                    self.snake_game.snake.grow();
+                    self.score += BONUS_FRUIT_SCORE;
+                } else if hit.downcast_ref::<Obstacle>().is_some() {
+                    return false; // Game over
                 }
             }
 
-            // Spawn new apple if needed
-            if self.snake_game.entities.is_empty() {
-                _ = self.snake_game.generate_entity(named!(Apple));
+            // Age out bonus fruits that have been on the board too long
+            self.snake_game
+                .entities
+                .retain_mut(|entity| match entity.downcast_mut::<BonusFruit>() {
+                    Some(bonus) if bonus.ticks_left == 0 => false,
+                    Some(bonus) => {
+                        bonus.ticks_left -= 1;
+                        true
+                    }
+                    None => true,
+                });
+
+            // Keep exactly one apple on the board at all times
+            let has_apple = self
+                .snake_game
+                .entities
+                .iter()
+                .any(|entity| entity.downcast_ref::<Apple>().is_some());
+            if !has_apple && !self.spawn_entity(Apple::new) && self.board_full() {
+                self.won = true;
+                return false; // No free cell left, even discounting transient entities
+            }
+
+            // Occasionally roll a bonus fruit if one isn't already out
+            let has_bonus = self
+                .snake_game
+                .entities
+                .iter()
+                .any(|entity| entity.downcast_ref::<BonusFruit>().is_some());
+            if !has_bonus && rand::gen_range(0.0, 1.0) < BONUS_FRUIT_CHANCE {
+                self.spawn_entity(BonusFruit::new);
             }
 
             // Reset movement tracking
@@ -93,7 +336,7 @@ impl Game {
             if input_moved {
                 self.update_timer = 0.0;
             } else {
-                self.update_timer -= BASE_UPDATE_INTERVAL;
+                self.update_timer -= move_interval;
             }
         }
 
@@ -102,6 +345,8 @@ impl Game {
 
     /// Render the current game state
     pub fn draw(&self) {
+        self.draw_border();
+
         // Draw snake
         for segment in &self.snake_game.snake.body {
             draw_rectangle(
@@ -113,17 +358,27 @@ impl Game {
             );
         }
 
-        // Draw apple if present
-        if let Some(apple) = self.snake_game.entities.first() {
-            draw_circle(
-                (apple.x() as f32 * GRID_WIDTH) + GRID_WIDTH / 2.0,
-                (apple.y() as f32 * GRID_WIDTH) + GRID_WIDTH / 2.0,
-                GRID_WIDTH / 2.0,
-                YELLOW,
-            );
+        // Draw each entity according to its kind
+        for entity in &self.snake_game.entities {
+            let cx = (entity.x() as f32 * GRID_WIDTH) + GRID_WIDTH / 2.0;
+            let cy = (entity.y() as f32 * GRID_WIDTH) + GRID_WIDTH / 2.0;
+
+            if entity.downcast_ref::<Apple>().is_some() {
+                draw_circle(cx, cy, GRID_WIDTH / 2.0, YELLOW);
+            } else if entity.downcast_ref::<BonusFruit>().is_some() {
+                draw_circle(cx, cy, GRID_WIDTH / 2.0, MAGENTA);
+            } else if entity.downcast_ref::<Obstacle>().is_some() {
+                draw_rectangle(
+                    entity.x() as f32 * GRID_WIDTH,
+                    entity.y() as f32 * GRID_WIDTH,
+                    GRID_WIDTH,
+                    GRID_WIDTH,
+                    GRAY,
+                );
+            }
         }
 
-        // Draw score
+        // Draw score and high score
         draw_text(
             &format!("Score: {}", self.score),
             20.0,
@@ -131,7 +386,109 @@ impl Game {
             30.0,
             WHITE,
         );
+        draw_text(
+            &format!("Best: {}", self.high_score),
+            20.0,
+            50.0,
+            30.0,
+            WHITE,
+        );
     }
+
+    /// Draw an outline along the board's outer edge: a solid line a quarter
+    /// cell thick in `Walls` mode, a dim dashed outline in `WrapAround` mode
+    /// since the edge isn't actually lethal. The outline is a thin line over
+    /// the edge of the board, not a separate frame a full cell wide.
+    fn draw_border(&self) {
+        let width = self.columns as f32 * GRID_WIDTH;
+        let height = self.rows as f32 * GRID_WIDTH;
+
+        match self.board_mode {
+            BoardMode::Walls => {
+                draw_rectangle_lines(0.0, 0.0, width, height, GRID_WIDTH / 4.0, WHITE);
+            }
+            BoardMode::WrapAround => {
+                let dash = GRID_WIDTH;
+                let mut x = 0.0;
+                while x < width {
+                    draw_line(x, 0.0, x + dash / 2.0, 0.0, 2.0, DARKGRAY);
+                    draw_line(x, height, x + dash / 2.0, height, 2.0, DARKGRAY);
+                    x += dash;
+                }
+                let mut y = 0.0;
+                while y < height {
+                    draw_line(0.0, y, 0.0, y + dash / 2.0, 2.0, DARKGRAY);
+                    draw_line(width, y, width, y + dash / 2.0, 2.0, DARKGRAY);
+                    y += dash;
+                }
+            }
+        }
+    }
+}
+
+/// The overall phase the main loop is in, independent of any in-progress `Game`
+enum GameState {
+    Menu,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+/// Draw the start screen shown before a `Game` has been created
+fn draw_menu_screen(high_score: u32, board_mode: BoardMode) {
+    let mode_label = match board_mode {
+        BoardMode::Walls => "Walls (TAB to switch)",
+        BoardMode::WrapAround => "Wrap-around (TAB to switch)",
+    };
+
+    draw_text_centered("SNAKE", screen_height() / 2.0 - 60.0, 60.0, WHITE);
+    draw_text_centered(
+        &format!("Best: {}", high_score),
+        screen_height() / 2.0,
+        30.0,
+        WHITE,
+    );
+    draw_text_centered(
+        &format!("Mode: {}", mode_label),
+        screen_height() / 2.0 + 30.0,
+        24.0,
+        GRAY,
+    );
+    draw_text_centered(
+        "Press SPACE to start",
+        screen_height() / 2.0 + 70.0,
+        24.0,
+        GRAY,
+    );
+}
+
+/// Draw the "frozen" overlay shown while `Playing` is suspended
+fn draw_paused_overlay() {
+    draw_text_centered("PAUSED", screen_height() / 2.0, 40.0, WHITE);
+}
+
+/// Draw the end-of-run screen with the final and best scores
+fn draw_game_over_screen(score: u32, high_score: u32, won: bool) {
+    let title = if won { "YOU FILLED THE BOARD!" } else { "GAME OVER" };
+    draw_text_centered(title, screen_height() / 2.0 - 60.0, 50.0, RED);
+    draw_text_centered(
+        &format!("Score: {}  Best: {}", score, high_score),
+        screen_height() / 2.0,
+        30.0,
+        WHITE,
+    );
+    draw_text_centered(
+        "Press SPACE to restart",
+        screen_height() / 2.0 + 40.0,
+        24.0,
+        GRAY,
+    );
+}
+
+/// Draw `text` horizontally centered on screen at the given vertical position
+fn draw_text_centered(text: &str, y: f32, font_size: f32, color: Color) {
+    let width = measure_text(text, None, font_size as u16, 1.0).width;
+    draw_text(text, (screen_width() - width) / 2.0, y, font_size, color);
 }
 
 #[macroquad::main("Snake Game")]
@@ -141,21 +498,64 @@ async fn main() {
         (screen_width() / GRID_WIDTH).floor() as i16 - 1,
         (screen_height() / GRID_WIDTH).floor() as i16 - 1,
     );
-    let mut game = Game::new(columns, rows);
+
+    let mut state = GameState::Menu;
+    let mut game: Option<Game> = None;
+    let mut high_score: u32 = load_high_score();
+    let mut board_mode = BoardMode::Walls;
 
     loop {
         clear_background(BLACK);
 
-        // Update game state
-        if !game.update(get_frame_time()) {
-            // Game over - reset
-            game.high_score = game.high_score.max(game.score);
-            game = Game::new(columns, rows);
+        match state {
+            GameState::Menu => {
+                draw_menu_screen(high_score, board_mode);
+                if is_key_pressed(KeyCode::Tab) {
+                    board_mode = match board_mode {
+                        BoardMode::Walls => BoardMode::WrapAround,
+                        BoardMode::WrapAround => BoardMode::Walls,
+                    };
+                }
+                if is_key_pressed(KeyCode::Space) {
+                    let mut new_game = Game::new(columns, rows, board_mode);
+                    new_game.high_score = high_score;
+                    game = Some(new_game);
+                    state = GameState::Playing;
+                }
+            }
+            GameState::Playing => {
+                if let Some(current) = game.as_mut() {
+                    if is_key_pressed(KeyCode::P) {
+                        state = GameState::Paused;
+                    } else if !current.update(get_frame_time()) {
+                        if current.score > high_score {
+                            high_score = current.score;
+                            store_high_score(high_score);
+                        }
+                        state = GameState::GameOver;
+                    }
+                    current.draw();
+                }
+            }
+            GameState::Paused => {
+                if let Some(current) = game.as_ref() {
+                    current.draw();
+                }
+                draw_paused_overlay();
+                if is_key_pressed(KeyCode::P) {
+                    state = GameState::Playing;
+                }
+            }
+            GameState::GameOver => {
+                if let Some(current) = game.as_ref() {
+                    draw_game_over_screen(current.score, high_score, current.won);
+                }
+                if is_key_pressed(KeyCode::Space) {
+                    state = GameState::Menu;
+                }
+            }
         }
 
-        // Draw game
-        game.draw();
-
         next_frame().await;
     }
 }
\ No newline at end of file